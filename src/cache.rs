@@ -0,0 +1,120 @@
+//! Binary cache format for regions, so pre-built masks (especially large
+//! polygons) can be stored and reloaded without re-parsing DS9 text.
+//! Reloaded data is still validated before a region is built from it.
+
+use pyo3::exceptions::PyValueError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct PolygonData {
+    pub(crate) ra_verticies: Vec<f64>,
+    pub(crate) dec_verticies: Vec<f64>,
+}
+
+impl PolygonData {
+    /// Checks the invariants `Polygon::new`/`SphericalPolygon::new` assume,
+    /// so a truncated or otherwise corrupt-but-still-valid-bincode cache
+    /// file is rejected here instead of panicking deeper in construction.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.ra_verticies.len() != self.dec_verticies.len() {
+            return Err(format!(
+                "polygon cache has mismatched vertex counts: {} ra vs {} dec",
+                self.ra_verticies.len(),
+                self.dec_verticies.len()
+            ));
+        }
+        if self.ra_verticies.len() < 3 {
+            return Err(format!(
+                "polygon cache needs at least 3 vertices, found {}",
+                self.ra_verticies.len()
+            ));
+        }
+        if self
+            .ra_verticies
+            .iter()
+            .chain(&self.dec_verticies)
+            .any(|coord| !coord.is_finite())
+        {
+            return Err("polygon cache has a non-finite coordinate".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ApertureData {
+    pub(crate) ra_center: f64,
+    pub(crate) dec_center: f64,
+    pub(crate) radius_deg: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct AnulusData {
+    pub(crate) ra_center: f64,
+    pub(crate) dec_center: f64,
+    pub(crate) inner_radius_deg: f64,
+    pub(crate) outer_radius_deg: f64,
+}
+
+pub(crate) fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+    bincode::serialize(value).expect("region cache data is always serializable")
+}
+
+pub(crate) fn decode<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> pyo3::PyResult<T> {
+    bincode::deserialize(bytes).map_err(|e| PyValueError::new_err(format!("invalid region cache data: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anulus, Aperture, Polygon};
+
+    #[test]
+    fn polygon_round_trips_through_the_binary_cache() {
+        let original = Polygon::new(vec![0.0, 1.0, 1.0], vec![0.0, 0.0, 1.0]);
+        let restored = Polygon::from_bytes(&original.to_bytes()).unwrap();
+        assert_eq!(restored.ra_verticies, original.ra_verticies);
+        assert_eq!(restored.dec_verticies, original.dec_verticies);
+    }
+
+    #[test]
+    fn aperture_round_trips_through_the_binary_cache() {
+        let original = Aperture::new(187.5, 12.3, 0.5);
+        let restored = Aperture::from_bytes(&original.to_bytes()).unwrap();
+        assert_eq!(restored.ra_center, original.ra_center);
+        assert_eq!(restored.dec_center, original.dec_center);
+        assert_eq!(restored.radius_deg, original.radius_deg);
+    }
+
+    #[test]
+    fn anulus_round_trips_through_the_binary_cache() {
+        let original = Anulus::new(10.0, -5.0, 1.0, 2.0);
+        let restored = Anulus::from_bytes(&original.to_bytes()).unwrap();
+        assert_eq!(restored.ra_center, original.ra_center);
+        assert_eq!(restored.inner_radius_deg, original.inner_radius_deg);
+        assert_eq!(restored.outer_radius_deg, original.outer_radius_deg);
+    }
+
+    #[test]
+    fn from_bytes_rejects_corrupt_data_instead_of_panicking() {
+        let too_few_vertices = encode(&PolygonData {
+            ra_verticies: vec![0.0, 1.0],
+            dec_verticies: vec![0.0, 1.0],
+        });
+        assert!(Polygon::from_bytes(&too_few_vertices).is_err());
+
+        let mismatched_lengths = encode(&PolygonData {
+            ra_verticies: vec![0.0, 1.0, 2.0],
+            dec_verticies: vec![0.0, 1.0],
+        });
+        assert!(Polygon::from_bytes(&mismatched_lengths).is_err());
+
+        let non_finite = encode(&PolygonData {
+            ra_verticies: vec![0.0, 1.0, f64::NAN],
+            dec_verticies: vec![0.0, 1.0, 2.0],
+        });
+        assert!(Polygon::from_bytes(&non_finite).is_err());
+
+        assert!(Polygon::from_bytes(b"not bincode at all").is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use astroxide::regions::{PointLocation, SphericalAnulus, SphericalAperture, SphericalPolygon};
+use pyo3::prelude::*;
+use rstar::{RTree, RTreeObject, AABB};
+
+use crate::{Anulus, Aperture, Polygon};
+
+/// One of the region kinds that can live inside a [`RegionCollection`], holding
+/// its own copy of the underlying `astroxide` geometry so the collection can
+/// outlive the Python objects it was built from.
+enum Region {
+    Polygon(SphericalPolygon),
+    Aperture(SphericalAperture),
+    Anulus(SphericalAnulus),
+}
+
+impl Region {
+    fn locate_point(&self, ra: f64, dec: f64) -> PointLocation {
+        match self {
+            Region::Polygon(p) => p.locate_point(ra, dec),
+            Region::Aperture(a) => a.locate_point(ra, dec),
+            Region::Anulus(a) => a.locate_point(ra, dec),
+        }
+    }
+}
+
+/// An entry in the R-tree: the axis-aligned (RA, Dec) box of a region, tagged
+/// with the index of the region it bounds in [`RegionCollection::regions`].
+struct BoxEntry {
+    index: usize,
+    envelope: AABB<[f64; 2]>,
+}
+
+impl RTreeObject for BoxEntry {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+/// Splits a (possibly RA-wrapping) bounding box into one or two boxes that
+/// stay within RA in `[0, 360]`, and widens to the full RA range whenever the
+/// Dec extent touches a pole (where every RA value is equally "close").
+fn normalize_box(ra_min: f64, ra_max: f64, dec_min: f64, dec_max: f64) -> Vec<AABB<[f64; 2]>> {
+    let dec_min = dec_min.max(-90.0);
+    let dec_max = dec_max.min(90.0);
+
+    if dec_min <= -90.0 || dec_max >= 90.0 {
+        return vec![AABB::from_corners([0.0, dec_min], [360.0, dec_max])];
+    }
+
+    if ra_min < 0.0 {
+        vec![
+            AABB::from_corners([ra_min + 360.0, dec_min], [360.0, dec_max]),
+            AABB::from_corners([0.0, dec_min], [ra_max, dec_max]),
+        ]
+    } else if ra_max > 360.0 {
+        vec![
+            AABB::from_corners([ra_min, dec_min], [360.0, dec_max]),
+            AABB::from_corners([0.0, dec_min], [ra_max - 360.0, dec_max]),
+        ]
+    } else {
+        vec![AABB::from_corners([ra_min, dec_min], [ra_max, dec_max])]
+    }
+}
+
+fn cap_boxes(ra_center: f64, dec_center: f64, radius_deg: f64) -> Vec<AABB<[f64; 2]>> {
+    let dec_min = dec_center - radius_deg;
+    let dec_max = dec_center + radius_deg;
+    let cos_dec = dec_center.to_radians().cos().abs().max(1e-9);
+    // The cap's widest RA offset satisfies sin(offset) = sin(r) / cos(dec);
+    // a ratio of 1 or more means the cap reaches a pole.
+    let sin_ratio = radius_deg.to_radians().sin() / cos_dec;
+    let ra_half = if sin_ratio >= 1.0 {
+        180.0
+    } else {
+        sin_ratio.asin().to_degrees()
+    };
+    normalize_box(
+        ra_center - ra_half,
+        ra_center + ra_half,
+        dec_min,
+        dec_max,
+    )
+}
+
+fn polygon_boxes(ra_verticies: &[f64], dec_verticies: &[f64]) -> Vec<AABB<[f64; 2]>> {
+    let ra_min = ra_verticies.iter().cloned().fold(f64::INFINITY, f64::min);
+    let ra_max = ra_verticies
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let dec_min = dec_verticies.iter().cloned().fold(f64::INFINITY, f64::min);
+    let dec_max = dec_verticies
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+    normalize_box(ra_min, ra_max, dec_min, dec_max)
+}
+
+/// A heterogeneous collection of [`Polygon`], [`Aperture`], and [`Anulus`]
+/// regions, backed by an R-tree over each region's (RA, Dec) bounding box so
+/// that classifying a point against thousands of regions is near-log time
+/// instead of scanning every region.
+#[pyclass]
+pub struct RegionCollection {
+    regions: Vec<Region>,
+    tree: RTree<BoxEntry>,
+}
+
+#[pymethods]
+impl RegionCollection {
+    #[new]
+    pub fn new() -> Self {
+        RegionCollection {
+            regions: Vec::new(),
+            tree: RTree::new(),
+        }
+    }
+
+    /// Adds a polygon to the collection and indexes its vertex bounding box.
+    pub fn add_polygon(&mut self, polygon: &Polygon) {
+        let boxes = polygon_boxes(&polygon.ra_verticies, &polygon.dec_verticies);
+        self.insert(
+            Region::Polygon(
+                SphericalPolygon::new(
+                    polygon.ra_verticies.clone(),
+                    polygon.dec_verticies.clone(),
+                )
+                .unwrap(),
+            ),
+            boxes,
+        );
+    }
+
+    /// Adds an aperture to the collection and indexes its spherical-cap bounding box.
+    pub fn add_aperture(&mut self, aperture: &Aperture) {
+        let boxes = cap_boxes(aperture.ra_center, aperture.dec_center, aperture.radius_deg);
+        self.insert(
+            Region::Aperture(SphericalAperture::new(
+                aperture.ra_center,
+                aperture.dec_center,
+                aperture.radius_deg,
+            )),
+            boxes,
+        );
+    }
+
+    /// Adds an annulus to the collection, indexed by its outer-radius bounding box.
+    pub fn add_anulus(&mut self, anulus: &Anulus) {
+        let boxes = cap_boxes(
+            anulus.ra_center,
+            anulus.dec_center,
+            anulus.outer_radius_deg,
+        );
+        self.insert(
+            Region::Anulus(SphericalAnulus::new(
+                anulus.ra_center,
+                anulus.dec_center,
+                anulus.inner_radius_deg,
+                anulus.outer_radius_deg,
+            )),
+            boxes,
+        );
+    }
+
+    /// Returns the index of the first region containing `(ra, dec)`, or `None`.
+    ///
+    /// The R-tree yields candidates in its own node-traversal order, not
+    /// insertion order, so "first" is enforced explicitly by taking the
+    /// minimum matching index rather than the first one the tree happens to
+    /// visit.
+    pub fn locate_point(&self, ra: f64, dec: f64) -> Option<usize> {
+        self.candidates(ra, dec)
+            .filter(|&index| {
+                !matches!(self.regions[index].locate_point(ra, dec), PointLocation::Outside)
+            })
+            .min()
+    }
+
+    /// Batched form of [`RegionCollection::locate_point`].
+    pub fn locate_all(&self, ras: Vec<f64>, decs: Vec<f64>) -> Vec<Option<usize>> {
+        ras.iter()
+            .zip(decs.iter())
+            .map(|(&ra, &dec)| self.locate_point(ra, dec))
+            .collect()
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.regions.len()
+    }
+}
+
+impl RegionCollection {
+    fn insert(&mut self, region: Region, boxes: Vec<AABB<[f64; 2]>>) {
+        let index = self.regions.len();
+        self.regions.push(region);
+        for envelope in boxes {
+            self.tree.insert(BoxEntry { index, envelope });
+        }
+    }
+
+    fn candidates(&self, ra: f64, dec: f64) -> impl Iterator<Item = usize> + '_ {
+        self.tree
+            .locate_in_envelope_intersecting(&AABB::from_point([ra, dec]))
+            .map(|entry| entry.index)
+    }
+}
+
+impl Default for RegionCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anulus, Aperture};
+
+    #[test]
+    fn normalize_box_wraps_across_the_ra_seam() {
+        let boxes = normalize_box(-2.0, 3.0, -10.0, 10.0);
+        assert_eq!(boxes.len(), 2);
+        assert!(boxes
+            .iter()
+            .any(|b| b.lower()[0] == 358.0 && b.upper()[0] == 360.0));
+        assert!(boxes
+            .iter()
+            .any(|b| b.lower()[0] == 0.0 && b.upper()[0] == 3.0));
+    }
+
+    #[test]
+    fn normalize_box_expands_to_full_ra_range_at_the_pole() {
+        let boxes = cap_boxes(10.0, 89.5, 1.0);
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].lower()[0], 0.0);
+        assert_eq!(boxes[0].upper()[0], 360.0);
+    }
+
+    #[test]
+    fn locate_point_prefers_the_lowest_index_on_overlap() {
+        let mut collection = RegionCollection::new();
+        collection.add_aperture(&Aperture::new(10.0, 10.0, 5.0));
+        collection.add_aperture(&Aperture::new(10.0, 10.0, 5.0));
+        assert_eq!(collection.locate_point(10.0, 10.0), Some(0));
+    }
+
+    #[test]
+    fn locate_point_returns_none_outside_every_region() {
+        let mut collection = RegionCollection::new();
+        collection.add_anulus(&Anulus::new(0.0, 0.0, 1.0, 2.0));
+        assert_eq!(collection.locate_point(0.0, 0.0), None);
+    }
+}
@@ -0,0 +1,272 @@
+use astroxide::regions::{PointLocation, SphericalAnulus, SphericalAperture, SphericalPolygon};
+use pyo3::prelude::*;
+
+use crate::{Anulus, Aperture, Polygon, PointResult};
+
+/// Boolean operator joining two regions in a [`CompoundRegion`]'s operation tree.
+#[derive(Clone, Copy)]
+pub(crate) enum Op {
+    Union,
+    Intersection,
+    Difference,
+    Xor,
+}
+
+/// A node in a [`CompoundRegion`]'s operation tree: either a leaf region,
+/// kept alongside the raw parameters it was built from so the tree can be
+/// cloned when composed further, or a boolean combination of two children.
+pub(crate) enum Node {
+    Polygon {
+        polygon: SphericalPolygon,
+        ra_verticies: Vec<f64>,
+        dec_verticies: Vec<f64>,
+    },
+    Aperture {
+        aperture: SphericalAperture,
+        ra_center: f64,
+        dec_center: f64,
+        radius_deg: f64,
+    },
+    Anulus {
+        anulus: SphericalAnulus,
+        ra_center: f64,
+        dec_center: f64,
+        inner_radius_deg: f64,
+        outer_radius_deg: f64,
+    },
+    Op(Op, Box<Node>, Box<Node>),
+}
+
+impl Node {
+    pub(crate) fn polygon(ra_verticies: Vec<f64>, dec_verticies: Vec<f64>) -> Node {
+        let polygon = SphericalPolygon::new(ra_verticies.clone(), dec_verticies.clone()).unwrap();
+        Node::Polygon {
+            polygon,
+            ra_verticies,
+            dec_verticies,
+        }
+    }
+
+    pub(crate) fn aperture(ra_center: f64, dec_center: f64, radius_deg: f64) -> Node {
+        Node::Aperture {
+            aperture: SphericalAperture::new(ra_center, dec_center, radius_deg),
+            ra_center,
+            dec_center,
+            radius_deg,
+        }
+    }
+
+    pub(crate) fn anulus(
+        ra_center: f64,
+        dec_center: f64,
+        inner_radius_deg: f64,
+        outer_radius_deg: f64,
+    ) -> Node {
+        Node::Anulus {
+            anulus: SphericalAnulus::new(ra_center, dec_center, inner_radius_deg, outer_radius_deg),
+            ra_center,
+            dec_center,
+            inner_radius_deg,
+            outer_radius_deg,
+        }
+    }
+
+    fn locate(&self, ra: f64, dec: f64) -> PointResult {
+        match self {
+            Node::Polygon { polygon, .. } => from_location(polygon.locate_point(ra, dec)),
+            Node::Aperture { aperture, .. } => from_location(aperture.locate_point(ra, dec)),
+            Node::Anulus { anulus, .. } => from_location(anulus.locate_point(ra, dec)),
+            Node::Op(op, left, right) => combine(*op, left.locate(ra, dec), right.locate(ra, dec)),
+        }
+    }
+
+    fn clone_tree(&self) -> Node {
+        match self {
+            Node::Polygon {
+                ra_verticies,
+                dec_verticies,
+                ..
+            } => Node::polygon(ra_verticies.clone(), dec_verticies.clone()),
+            Node::Aperture {
+                ra_center,
+                dec_center,
+                radius_deg,
+                ..
+            } => Node::aperture(*ra_center, *dec_center, *radius_deg),
+            Node::Anulus {
+                ra_center,
+                dec_center,
+                inner_radius_deg,
+                outer_radius_deg,
+                ..
+            } => Node::anulus(*ra_center, *dec_center, *inner_radius_deg, *outer_radius_deg),
+            Node::Op(op, left, right) => {
+                Node::Op(*op, Box::new(left.clone_tree()), Box::new(right.clone_tree()))
+            }
+        }
+    }
+}
+
+fn from_location(location: PointLocation) -> PointResult {
+    match location {
+        PointLocation::Inside => PointResult::Inside,
+        PointLocation::Outside => PointResult::Outside,
+        PointLocation::OnBoundary => PointResult::Edge,
+    }
+}
+
+/// Combines two leaf/child results according to the boolean truth table for
+/// `op`, propagating `Edge` whenever a point lies on a contributing boundary
+/// that the operation doesn't cancel out.
+fn combine(op: Op, a: PointResult, b: PointResult) -> PointResult {
+    use PointResult::{Edge, Inside, Outside};
+    match op {
+        Op::Union => match (a, b) {
+            (Inside, _) | (_, Inside) => Inside,
+            (Edge, _) | (_, Edge) => Edge,
+            _ => Outside,
+        },
+        Op::Intersection => match (a, b) {
+            (Inside, Inside) => Inside,
+            (Outside, _) | (_, Outside) => Outside,
+            _ => Edge,
+        },
+        Op::Difference => match (a, b) {
+            (Outside, _) => Outside,
+            (_, Inside) => Outside,
+            (Inside, Outside) => Inside,
+            _ => Edge,
+        },
+        Op::Xor => {
+            let a_minus_b = combine(Op::Difference, a, b);
+            let b_minus_a = combine(Op::Difference, b, a);
+            combine(Op::Union, a_minus_b, b_minus_a)
+        }
+    }
+}
+
+/// Any region type that can be combined with `|`/`&`/`-`/`^` into a
+/// [`CompoundRegion`].
+#[derive(FromPyObject)]
+pub(crate) enum RegionOperand<'py> {
+    Polygon(PyRef<'py, Polygon>),
+    Aperture(PyRef<'py, Aperture>),
+    Anulus(PyRef<'py, Anulus>),
+    Compound(PyRef<'py, CompoundRegion>),
+}
+
+impl<'py> RegionOperand<'py> {
+    pub(crate) fn into_node(self) -> Node {
+        match self {
+            RegionOperand::Polygon(p) => p.to_node(),
+            RegionOperand::Aperture(a) => a.to_node(),
+            RegionOperand::Anulus(a) => a.to_node(),
+            RegionOperand::Compound(c) => c.node.clone_tree(),
+        }
+    }
+}
+
+/// A boolean composition of [`Polygon`], [`Aperture`], and [`Anulus`]
+/// regions, built via the `|`/`&`/`-`/`^` operators. Membership is evaluated
+/// compositionally: each query recurses into the operation tree and combines
+/// the children's `PointResult`s rather than computing new boundary geometry.
+#[pyclass]
+pub struct CompoundRegion {
+    node: Node,
+}
+
+#[pymethods]
+impl CompoundRegion {
+    pub fn is_inside(&self, ra_point: f64, dec_point: f64) -> PointResult {
+        self.node.locate(ra_point, dec_point)
+    }
+
+    pub fn locate_all(&self, ra_points: Vec<f64>, dec_points: Vec<f64>) -> Vec<PointResult> {
+        ra_points
+            .iter()
+            .zip(dec_points.iter())
+            .map(|(&ra, &dec)| self.node.locate(ra, dec))
+            .collect()
+    }
+
+    pub fn __or__(&self, other: RegionOperand) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Union, self.node.clone_tree(), other.into_node())
+    }
+
+    pub fn __and__(&self, other: RegionOperand) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Intersection, self.node.clone_tree(), other.into_node())
+    }
+
+    pub fn __sub__(&self, other: RegionOperand) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Difference, self.node.clone_tree(), other.into_node())
+    }
+
+    pub fn __xor__(&self, other: RegionOperand) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Xor, self.node.clone_tree(), other.into_node())
+    }
+}
+
+impl CompoundRegion {
+    pub(crate) fn new_op(op: Op, left: Node, right: Node) -> Self {
+        CompoundRegion {
+            node: Node::Op(op, Box::new(left), Box::new(right)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PointResult::{Edge, Inside, Outside};
+
+    #[test]
+    fn union_truth_table() {
+        assert_eq!(combine(Op::Union, Inside, Outside), Inside);
+        assert_eq!(combine(Op::Union, Outside, Inside), Inside);
+        assert_eq!(combine(Op::Union, Outside, Outside), Outside);
+        assert_eq!(combine(Op::Union, Outside, Edge), Edge);
+        assert_eq!(combine(Op::Union, Inside, Edge), Inside);
+    }
+
+    #[test]
+    fn intersection_truth_table() {
+        assert_eq!(combine(Op::Intersection, Inside, Outside), Outside);
+        assert_eq!(combine(Op::Intersection, Inside, Inside), Inside);
+        assert_eq!(combine(Op::Intersection, Edge, Inside), Edge);
+        assert_eq!(combine(Op::Intersection, Edge, Outside), Outside);
+        assert_eq!(combine(Op::Intersection, Edge, Edge), Edge);
+    }
+
+    #[test]
+    fn difference_truth_table() {
+        // a \ b
+        assert_eq!(combine(Op::Difference, Inside, Inside), Outside);
+        assert_eq!(combine(Op::Difference, Inside, Outside), Inside);
+        assert_eq!(combine(Op::Difference, Outside, Inside), Outside);
+        assert_eq!(combine(Op::Difference, Outside, Outside), Outside);
+        assert_eq!(combine(Op::Difference, Inside, Edge), Edge);
+        assert_eq!(combine(Op::Difference, Edge, Outside), Edge);
+    }
+
+    #[test]
+    fn xor_is_symmetric_and_matches_union_of_differences() {
+        for a in [Inside, Outside, Edge] {
+            for b in [Inside, Outside, Edge] {
+                assert_eq!(combine(Op::Xor, a, b), combine(Op::Xor, b, a));
+            }
+        }
+        assert_eq!(combine(Op::Xor, Inside, Inside), Outside);
+        assert_eq!(combine(Op::Xor, Inside, Outside), Inside);
+    }
+
+    #[test]
+    fn compound_region_evaluates_a_union_of_two_apertures() {
+        let left = Node::aperture(0.0, 0.0, 1.0);
+        let right = Node::aperture(10.0, 0.0, 1.0);
+        let region = CompoundRegion::new_op(Op::Union, left, right);
+
+        assert_eq!(region.is_inside(0.0, 0.0), Inside);
+        assert_eq!(region.is_inside(10.0, 0.0), Inside);
+        assert_eq!(region.is_inside(5.0, 0.0), Outside);
+    }
+}
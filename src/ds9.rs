@@ -0,0 +1,157 @@
+//! Import/export of the DS9/region-file text format, so regions built here
+//! can round-trip with DS9, TOPCAT, and astropy-region.
+
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::{Anulus, Aperture, Polygon};
+
+/// Splits a single DS9 region line such as `circle(187.5,12.3,0.5)` (an
+/// optional `fk5;`/`icrs;` coordinate-system prefix is tolerated) into its
+/// shape name and comma-separated numeric arguments.
+pub(crate) fn parse_line(text: &str) -> Result<(String, Vec<f64>), String> {
+    let line = text.trim();
+    let line = line
+        .strip_prefix("fk5;")
+        .or_else(|| line.strip_prefix("icrs;"))
+        .unwrap_or(line)
+        .trim();
+
+    let open = line
+        .find('(')
+        .ok_or_else(|| format!("not a DS9 region: {line}"))?;
+    let close = line
+        .rfind(')')
+        .ok_or_else(|| format!("unterminated region: {line}"))?;
+
+    let shape = line[..open].trim().to_lowercase();
+    let values = line[open + 1..close]
+        .split(',')
+        .map(|value| {
+            value
+                .trim()
+                .parse::<f64>()
+                .map_err(|e| format!("invalid number in region `{line}`: {e}"))
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+
+    Ok((shape, values))
+}
+
+/// Is this line a comment, blank, coordinate-system declaration, or the
+/// `global color=... ...` properties line DS9 always writes, rather than a
+/// region?
+fn is_skippable(line: &str) -> bool {
+    line.is_empty()
+        || line.starts_with('#')
+        || line.eq_ignore_ascii_case("fk5")
+        || line.eq_ignore_ascii_case("icrs")
+        || line.to_lowercase().starts_with("global")
+}
+
+/// Any region type that [`save_regions`] can write out.
+#[derive(FromPyObject)]
+pub(crate) enum DsRegion<'py> {
+    Polygon(PyRef<'py, Polygon>),
+    Aperture(PyRef<'py, Aperture>),
+    Anulus(PyRef<'py, Anulus>),
+}
+
+impl DsRegion<'_> {
+    fn to_ds9(&self) -> String {
+        match self {
+            DsRegion::Polygon(p) => p.to_ds9(),
+            DsRegion::Aperture(a) => a.to_ds9(),
+            DsRegion::Anulus(a) => a.to_ds9(),
+        }
+    }
+}
+
+/// Parses every region in a DS9 region file, in order, as a mix of
+/// [`Polygon`], [`Aperture`], and [`Anulus`] objects.
+#[pyfunction]
+pub fn load_regions(py: Python<'_>, path: &str) -> PyResult<Vec<Py<PyAny>>> {
+    let text = std::fs::read_to_string(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+
+    let mut regions = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if is_skippable(line) {
+            continue;
+        }
+        let (shape, _) = parse_line(line).map_err(PyValueError::new_err)?;
+        let region: Py<PyAny> = match shape.as_str() {
+            "circle" => Py::new(py, Aperture::from_ds9(line)?)?.into_any(),
+            "annulus" => Py::new(py, Anulus::from_ds9(line)?)?.into_any(),
+            "polygon" => Py::new(py, Polygon::from_ds9(line)?)?.into_any(),
+            other => return Err(PyValueError::new_err(format!("unsupported region: {other}"))),
+        };
+        regions.push(region);
+    }
+    Ok(regions)
+}
+
+/// Writes a list of regions out as a DS9 region file in the `fk5` frame.
+#[pyfunction]
+pub fn save_regions(path: &str, regions: Vec<DsRegion<'_>>) -> PyResult<()> {
+    let mut contents = String::from("fk5\n");
+    for region in &regions {
+        contents.push_str(&region.to_ds9());
+        contents.push('\n');
+    }
+    std::fs::write(path, contents).map_err(|e| PyIOError::new_err(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line_strips_the_coordinate_system_prefix() {
+        let (shape, values) = parse_line("fk5;circle(187.5,12.3,0.5)").unwrap();
+        assert_eq!(shape, "circle");
+        assert_eq!(values, vec![187.5, 12.3, 0.5]);
+    }
+
+    #[test]
+    fn is_skippable_matches_real_ds9_export_lines() {
+        assert!(is_skippable(""));
+        assert!(is_skippable("# Region file format: DS9 version 4.1"));
+        assert!(is_skippable("fk5"));
+        assert!(is_skippable(
+            "global color=green dashlist=8 3 width=1 font=\"helvetica 10 normal\""
+        ));
+        assert!(!is_skippable("circle(187.5,12.3,0.5)"));
+    }
+
+    #[test]
+    fn aperture_round_trips_through_ds9_text() {
+        let original = Aperture::new(187.5, 12.3, 0.5);
+        let restored = Aperture::from_ds9(&original.to_ds9()).unwrap();
+        assert_eq!(restored.ra_center, original.ra_center);
+        assert_eq!(restored.dec_center, original.dec_center);
+        assert_eq!(restored.radius_deg, original.radius_deg);
+    }
+
+    #[test]
+    fn anulus_round_trips_through_ds9_text() {
+        let original = Anulus::new(10.0, -5.0, 1.0, 2.0);
+        let restored = Anulus::from_ds9(&original.to_ds9()).unwrap();
+        assert_eq!(restored.ra_center, original.ra_center);
+        assert_eq!(restored.inner_radius_deg, original.inner_radius_deg);
+        assert_eq!(restored.outer_radius_deg, original.outer_radius_deg);
+    }
+
+    #[test]
+    fn polygon_round_trips_through_ds9_text() {
+        let original = Polygon::new(vec![0.0, 1.0, 1.0], vec![0.0, 0.0, 1.0]);
+        let restored = Polygon::from_ds9(&original.to_ds9()).unwrap();
+        assert_eq!(restored.ra_verticies, original.ra_verticies);
+        assert_eq!(restored.dec_verticies, original.dec_verticies);
+    }
+
+    #[test]
+    fn from_ds9_rejects_the_wrong_shape() {
+        assert!(Aperture::from_ds9("polygon(0,0,1,0,1,1)").is_err());
+    }
+}
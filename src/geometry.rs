@@ -0,0 +1,201 @@
+//! Small spherical-vector helpers shared by the area/centroid and
+//! convex-hull implementations.
+
+use std::f64::consts::PI;
+
+pub(crate) fn radec_to_unit(ra_deg: f64, dec_deg: f64) -> [f64; 3] {
+    let ra = ra_deg.to_radians();
+    let dec = dec_deg.to_radians();
+    [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()]
+}
+
+pub(crate) fn unit_to_radec(v: [f64; 3]) -> (f64, f64) {
+    let dec = v[2].clamp(-1.0, 1.0).asin();
+    let mut ra = v[1].atan2(v[0]).to_degrees();
+    if ra < 0.0 {
+        ra += 360.0;
+    }
+    (ra, dec.to_degrees())
+}
+
+pub(crate) fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub(crate) fn scale(a: [f64; 3], s: f64) -> [f64; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+pub(crate) fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+pub(crate) fn normalize(a: [f64; 3]) -> [f64; 3] {
+    scale(a, 1.0 / norm(a))
+}
+
+pub(crate) fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Solid angle (steradians) of a spherical cap of angular radius `radius_deg`.
+pub(crate) fn cap_steradians(radius_deg: f64) -> f64 {
+    2.0 * PI * (1.0 - radius_deg.to_radians().cos())
+}
+
+pub(crate) fn steradians_to_sq_deg(steradians: f64) -> f64 {
+    steradians * (180.0 / PI).powi(2)
+}
+
+/// Sign of the polygon's winding: positive when `vertices` winds
+/// counter-clockwise as seen from outside the sphere along its own
+/// centroid (the convention `Polygon`'s interior follows elsewhere, e.g.
+/// [`crate::hull::spherical_convex_hull`]), negative when clockwise.
+/// Computed from the vector-area sum `sum(v_i x v_{i+1})`, whose direction
+/// is the polygon's outward normal for a CCW winding, dotted against the
+/// (unnormalized) centroid direction — a cross-product orientation test
+/// rather than a magnitude bound, so it stays meaningful for polygons of
+/// any size, not just those under a hemisphere.
+fn winding_sign(vertices: &[[f64; 3]]) -> f64 {
+    let n = vertices.len();
+    let mut area_vector = [0.0, 0.0, 0.0];
+    let mut centroid = [0.0, 0.0, 0.0];
+    for i in 0..n {
+        area_vector = add(area_vector, cross(vertices[i], vertices[(i + 1) % n]));
+        centroid = add(centroid, vertices[i]);
+    }
+    dot(area_vector, centroid)
+}
+
+/// Spherical excess (steradians) of the polygon whose vertices are given as
+/// unit vectors, in order. The interior angle at each vertex is the angle
+/// between the tangents of the two great-circle arcs meeting there; the
+/// excess is `sum(interior angles) - (n - 2) * pi`. Degenerate (zero-length)
+/// edges are skipped.
+///
+/// This angle-sum is, by construction, always at most `2*pi` — it cannot
+/// by itself tell a polygon apart from its complement, since the interior
+/// angle at a vertex is the same figure regardless of which side is
+/// "inside". So orientation is determined separately via [`winding_sign`]:
+/// a clockwise winding means `vertices` bounds the *other* side of its own
+/// boundary, i.e. the region of area `4*pi - excess`.
+pub(crate) fn spherical_polygon_area(vertices: &[[f64; 3]]) -> f64 {
+    let n = vertices.len();
+    if n < 3 {
+        return 0.0;
+    }
+
+    let mut angle_sum = 0.0;
+    let mut n_angles = 0usize;
+    for i in 0..n {
+        let prev = vertices[(i + n - 1) % n];
+        let curr = vertices[i];
+        let next = vertices[(i + 1) % n];
+
+        let to_prev = sub(prev, scale(curr, dot(prev, curr)));
+        let to_next = sub(next, scale(curr, dot(next, curr)));
+        let (norm_prev, norm_next) = (norm(to_prev), norm(to_next));
+        if norm_prev < 1e-12 || norm_next < 1e-12 {
+            continue;
+        }
+
+        let cos_angle = (dot(to_prev, to_next) / (norm_prev * norm_next)).clamp(-1.0, 1.0);
+        angle_sum += cos_angle.acos();
+        n_angles += 1;
+    }
+
+    let excess = angle_sum - (n_angles as f64 - 2.0) * PI;
+    if winding_sign(vertices) < 0.0 {
+        4.0 * PI - excess
+    } else {
+        excess
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radec_unit_vector_round_trips() {
+        let (ra, dec) = (123.45, -17.5);
+        let (ra_out, dec_out) = unit_to_radec(radec_to_unit(ra, dec));
+        assert!((ra - ra_out).abs() < 1e-9);
+        assert!((dec - dec_out).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cap_steradians_matches_known_fractions_of_the_sphere() {
+        assert!((cap_steradians(90.0) - 2.0 * PI).abs() < 1e-9);
+        assert!((cap_steradians(180.0) - 4.0 * PI).abs() < 1e-9);
+    }
+
+    fn small_square_vertices() -> Vec<[f64; 3]> {
+        [(0.0, -1.0), (1.0, -1.0), (1.0, 1.0), (0.0, 1.0)]
+            .into_iter()
+            .map(|(ra, dec)| radec_to_unit(ra, dec))
+            .collect()
+    }
+
+    #[test]
+    fn reversed_winding_gives_the_complementary_area() {
+        let forward = small_square_vertices();
+        let mut reversed = forward.clone();
+        reversed.reverse();
+
+        let forward_area = spherical_polygon_area(&forward);
+        let reversed_area = spherical_polygon_area(&reversed);
+
+        // A 1x2 degree patch is a small sliver of the sphere, not most of it.
+        assert!(forward_area < 2.0 * PI);
+        // Reversing the winding flips which side of the same boundary is
+        // "inside", so the two areas are complementary, not equal.
+        assert!((forward_area + reversed_area - 4.0 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn area_over_half_the_sky_is_computed_correctly_not_as_its_complement() {
+        // A 72-vertex ring at dec=-10 deg bounds (in the direction wound
+        // below) the cap north of dec=-10, which covers more than half the
+        // sphere: steradians = 2*pi*(1 - sin(-10 deg)) ~= 7.374, i.e. a
+        // fraction of the sphere of ~0.5868, not the complementary south
+        // cap's ~0.4132 that the old excess-only formula silently returned.
+        let ring: Vec<[f64; 3]> = (0..360)
+            .step_by(5)
+            .map(|ra| radec_to_unit(ra as f64, -10.0))
+            .collect();
+
+        let area = spherical_polygon_area(&ring);
+        let true_north_cap = 2.0 * PI * (1.0 - (-10f64).to_radians().sin());
+
+        assert!(area > 2.0 * PI);
+        assert!((area - true_north_cap).abs() / true_north_cap < 1e-3);
+
+        // The reverse winding bounds the complementary south cap instead.
+        let mut reversed = ring.clone();
+        reversed.reverse();
+        let reversed_area = spherical_polygon_area(&reversed);
+        assert!((area + reversed_area - 4.0 * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn degenerate_polygon_has_zero_area() {
+        assert_eq!(spherical_polygon_area(&[]), 0.0);
+        assert_eq!(
+            spherical_polygon_area(&[radec_to_unit(0.0, 0.0), radec_to_unit(1.0, 0.0)]),
+            0.0
+        );
+    }
+}
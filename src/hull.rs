@@ -0,0 +1,211 @@
+//! Builds the spherical convex hull of a cloud of RA/Dec points: lift each
+//! point to a unit 3-vector, gnomonically project onto the plane tangent to
+//! the point cloud's mean direction (great circles map to straight lines,
+//! so the planar hull of the projection equals the hull on the sphere),
+//! take the 2D convex hull of the projection, and map the hull vertices
+//! back to RA/Dec. A gnomonic projection is only defined on an open
+//! hemisphere centered on the projection axis, so point clouds that don't
+//! fit within one are rejected rather than silently hulled wrong.
+
+use crate::geometry;
+
+/// Monotone-chain 2D convex hull, returning the indices of `points` that
+/// form the hull boundary in counter-clockwise order.
+fn convex_hull_2d(points: &[(f64, f64)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| points[a].partial_cmp(&points[b]).unwrap());
+
+    fn cross(o: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    }
+
+    if order.len() < 3 {
+        return order;
+    }
+
+    let mut lower: Vec<usize> = Vec::new();
+    for &i in &order {
+        while lower.len() >= 2
+            && cross(
+                points[lower[lower.len() - 2]],
+                points[lower[lower.len() - 1]],
+                points[i],
+            ) <= 0.0
+        {
+            lower.pop();
+        }
+        lower.push(i);
+    }
+
+    let mut upper: Vec<usize> = Vec::new();
+    for &i in order.iter().rev() {
+        while upper.len() >= 2
+            && cross(
+                points[upper[upper.len() - 2]],
+                points[upper[upper.len() - 1]],
+                points[i],
+            ) <= 0.0
+        {
+            upper.pop();
+        }
+        upper.push(i);
+    }
+
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+/// Computes the spherical convex hull of `(ras, decs)`, returning the hull
+/// vertices' RA/Dec in winding order suitable for [`crate::Polygon::new`].
+pub(crate) fn spherical_convex_hull(ras: &[f64], decs: &[f64]) -> Result<(Vec<f64>, Vec<f64>), String> {
+    if ras.len() != decs.len() {
+        return Err("ras and decs must have the same length".to_string());
+    }
+
+    let mut unique: Vec<[f64; 3]> = Vec::new();
+    for (&ra, &dec) in ras.iter().zip(decs) {
+        let point = geometry::radec_to_unit(ra, dec);
+        let is_duplicate = unique
+            .iter()
+            .any(|&u| geometry::dot(u, point) > 1.0 - 1e-12);
+        if !is_duplicate {
+            unique.push(point);
+        }
+    }
+
+    if unique.len() < 3 {
+        return Err("convex_hull needs at least 3 distinct points".to_string());
+    }
+
+    let centroid = unique.iter().copied().fold([0.0, 0.0, 0.0], geometry::add);
+    let centroid_norm = geometry::norm(centroid);
+    if centroid_norm < 1e-9 {
+        return Err(
+            "points are too symmetric about the origin to define a hull direction".to_string(),
+        );
+    }
+    let axis = geometry::scale(centroid, 1.0 / centroid_norm);
+
+    // Any vector not parallel to `axis` gives a tangent-plane basis via two cross products.
+    let helper = if axis[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let u = geometry::normalize(geometry::cross(helper, axis));
+    let v = geometry::cross(axis, u);
+
+    // Gnomonic (central) projection from the origin through each point onto
+    // the plane tangent to `axis`: dividing by the depth along `axis` is
+    // what makes great circles project to straight lines. It's only defined
+    // for points strictly inside the hemisphere centered on `axis`.
+    let mut projected = Vec::with_capacity(unique.len());
+    for &p in &unique {
+        let depth = geometry::dot(p, axis);
+        if depth <= 1e-9 {
+            return Err(
+                "points span more than a hemisphere around their mean direction; convex_hull \
+                 requires the whole cloud to fit within an open hemisphere"
+                    .to_string(),
+            );
+        }
+        projected.push((geometry::dot(p, u) / depth, geometry::dot(p, v) / depth));
+    }
+
+    let mut hull_indices = convex_hull_2d(&projected);
+    if hull_indices.len() < 3 {
+        return Err("points are collinear on a great circle".to_string());
+    }
+
+    // `(u, v, axis)` is right-handed by construction, so a hull that winds
+    // counter-clockwise in the projected plane faces outward along `axis`.
+    // Guard against degenerate projections flipping that rather than assume it.
+    if signed_area(&projected, &hull_indices) < 0.0 {
+        hull_indices.reverse();
+    }
+
+    let mut ra_out = Vec::with_capacity(hull_indices.len());
+    let mut dec_out = Vec::with_capacity(hull_indices.len());
+    for &i in &hull_indices {
+        let (ra, dec) = geometry::unit_to_radec(unique[i]);
+        ra_out.push(ra);
+        dec_out.push(dec);
+    }
+    Ok((ra_out, dec_out))
+}
+
+/// Twice the signed area of the polygon `indices` traces through `points`
+/// (shoelace formula); positive for counter-clockwise winding.
+fn signed_area(points: &[(f64, f64)], indices: &[usize]) -> f64 {
+    let n = indices.len();
+    let mut total = 0.0;
+    for i in 0..n {
+        let (x1, y1) = points[indices[i]];
+        let (x2, y2) = points[indices[(i + 1) % n]];
+        total += x1 * y2 - x2 * y1;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hull_of_a_spread_out_cloud_keeps_only_the_extreme_points() {
+        // A ring of points around (0, 0) plus one point at the centre: the
+        // centre point must not survive into the hull.
+        let mut ras = vec![0.0];
+        let mut decs = vec![0.0];
+        for deg in (0..360).step_by(30) {
+            let angle = (deg as f64).to_radians();
+            ras.push(10.0 * angle.cos());
+            decs.push(10.0 * angle.sin());
+        }
+
+        let (hull_ras, hull_decs) = spherical_convex_hull(&ras, &decs).unwrap();
+        assert_eq!(hull_ras.len(), 12);
+        assert_eq!(hull_decs.len(), 12);
+        // The interior point (0, 0) must have been discarded as non-extreme.
+        assert!(hull_ras
+            .iter()
+            .zip(&hull_decs)
+            .all(|(&ra, &dec)| ra.hypot(dec) > 1.0));
+    }
+
+    #[test]
+    fn hull_is_wound_counter_clockwise_facing_outward() {
+        let ras = vec![0.0, 1.0, 1.0, 0.0];
+        let decs = vec![0.0, 0.0, 1.0, 1.0];
+        let (hull_ras, hull_decs) = spherical_convex_hull(&ras, &decs).unwrap();
+
+        let vertices: Vec<[f64; 3]> = hull_ras
+            .iter()
+            .zip(&hull_decs)
+            .map(|(&ra, &dec)| geometry::radec_to_unit(ra, dec))
+            .collect();
+        let area = geometry::spherical_polygon_area(&vertices);
+        assert!(area > 0.0);
+        // A clockwise winding would bound the complement (nearly the whole sphere).
+        assert!(area < 2.0 * std::f64::consts::PI);
+    }
+
+    #[test]
+    fn rejects_points_spanning_more_than_a_hemisphere() {
+        // A non-degenerate centroid, but one point still lies in the far
+        // hemisphere relative to it: the gnomonic projection must reject
+        // this rather than silently hull it wrong.
+        let ras = vec![0.0, 120.0, 200.0];
+        let decs = vec![0.0, 0.0, 0.0];
+        assert!(spherical_convex_hull(&ras, &decs).is_err());
+    }
+
+    #[test]
+    fn rejects_fewer_than_three_distinct_points() {
+        let ras = vec![0.0, 0.0, 0.0];
+        let decs = vec![0.0, 0.0, 0.0];
+        assert!(spherical_convex_hull(&ras, &decs).is_err());
+    }
+}
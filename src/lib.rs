@@ -1,7 +1,23 @@
 use astroxide::regions::{PointLocation, SphericalAnulus, SphericalAperture, SphericalPolygon};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-#[pyclass]
+mod cache;
+mod collection;
+mod compound;
+mod ds9;
+mod geometry;
+mod hull;
+mod parallel;
+
+pub use collection::RegionCollection;
+pub use compound::CompoundRegion;
+pub use ds9::{load_regions, save_regions};
+
+use compound::{Node, Op, RegionOperand};
+
+#[pyclass(eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PointResult {
     Inside,
     Outside,
@@ -11,14 +27,21 @@ pub enum PointResult {
 #[pyclass]
 pub struct Polygon {
     polygon: SphericalPolygon,
+    pub(crate) ra_verticies: Vec<f64>,
+    pub(crate) dec_verticies: Vec<f64>,
 }
 
 #[pymethods]
 impl Polygon {
     #[new]
     pub fn new(ra_verticies: Vec<f64>, dec_verticies: Vec<f64>) -> Self {
-        let polygon = SphericalPolygon::new(ra_verticies, dec_verticies).unwrap();
-        Polygon { polygon }
+        let polygon =
+            SphericalPolygon::new(ra_verticies.clone(), dec_verticies.clone()).unwrap();
+        Polygon {
+            polygon,
+            ra_verticies,
+            dec_verticies,
+        }
     }
     pub fn is_inside(&self, ra_point: f64, dec_point: f64) -> PointResult {
         match self.polygon.locate_point(ra_point, dec_point) {
@@ -28,23 +51,129 @@ impl Polygon {
         }
     }
 
-    pub fn locate_all(&self, ra_points: Vec<f64>, dec_points: Vec<f64>) -> Vec<PointResult> {
-        let results = self.polygon.locate_points(ra_points.clone(), dec_points);
-        let mut locations = Vec::with_capacity(ra_points.len());
-        for result in results {
-            match result {
-                PointLocation::Inside => locations.push(PointResult::Inside),
-                PointLocation::Outside => locations.push(PointResult::Outside),
-                PointLocation::OnBoundary => locations.push(PointResult::Edge),
-            }
+    /// Classifies each (ra, dec) pair in parallel with Rayon. `n_threads`
+    /// defaults to all available cores; pass `1` to run serially.
+    #[pyo3(signature = (ra_points, dec_points, n_threads=None))]
+    pub fn locate_all(
+        &self,
+        py: Python<'_>,
+        ra_points: Vec<f64>,
+        dec_points: Vec<f64>,
+        n_threads: Option<usize>,
+    ) -> Vec<PointResult> {
+        py.allow_threads(|| {
+            parallel::classify(n_threads, &ra_points, &dec_points, |ra, dec| {
+                self.is_inside(ra, dec)
+            })
+        })
+    }
+
+    /// Sky area of the polygon, via the spherical excess of its vertices.
+    pub fn area_sq_deg(&self) -> f64 {
+        let vertices: Vec<[f64; 3]> = self
+            .ra_verticies
+            .iter()
+            .zip(&self.dec_verticies)
+            .map(|(&ra, &dec)| geometry::radec_to_unit(ra, dec))
+            .collect();
+        geometry::steradians_to_sq_deg(geometry::spherical_polygon_area(&vertices))
+    }
+
+    /// RA/Dec of the normalized sum of the vertex unit vectors.
+    pub fn centroid(&self) -> (f64, f64) {
+        let sum = self
+            .ra_verticies
+            .iter()
+            .zip(&self.dec_verticies)
+            .map(|(&ra, &dec)| geometry::radec_to_unit(ra, dec))
+            .fold([0.0, 0.0, 0.0], geometry::add);
+        geometry::unit_to_radec(geometry::normalize(sum))
+    }
+
+    pub fn __or__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Union, self.to_node(), other.into_node())
+    }
+
+    pub fn __and__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Intersection, self.to_node(), other.into_node())
+    }
+
+    pub fn __sub__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Difference, self.to_node(), other.into_node())
+    }
+
+    pub fn __xor__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Xor, self.to_node(), other.into_node())
+    }
+
+    /// Parses a DS9 `polygon(ra1,dec1,ra2,dec2,...)` region.
+    #[staticmethod]
+    pub fn from_ds9(text: &str) -> PyResult<Self> {
+        let (shape, values) = ds9::parse_line(text).map_err(PyValueError::new_err)?;
+        if shape != "polygon" {
+            return Err(PyValueError::new_err(format!(
+                "expected a DS9 polygon region, found `{shape}`"
+            )));
         }
-        locations
+        if values.len() < 6 || values.len() % 2 != 0 {
+            return Err(PyValueError::new_err(
+                "polygon needs at least 3 (ra, dec) vertex pairs",
+            ));
+        }
+        let ra_verticies = values.iter().step_by(2).copied().collect();
+        let dec_verticies = values.iter().skip(1).step_by(2).copied().collect();
+        Ok(Polygon::new(ra_verticies, dec_verticies))
+    }
+
+    /// Renders this polygon as a DS9 `polygon(...)` region line.
+    pub fn to_ds9(&self) -> String {
+        let coords: Vec<String> = self
+            .ra_verticies
+            .iter()
+            .zip(&self.dec_verticies)
+            .flat_map(|(&ra, &dec)| [format!("{ra:.6}"), format!("{dec:.6}")])
+            .collect();
+        format!("polygon({})", coords.join(","))
+    }
+
+    /// Serializes this polygon to a compact binary cache format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        cache::encode(&cache::PolygonData {
+            ra_verticies: self.ra_verticies.clone(),
+            dec_verticies: self.dec_verticies.clone(),
+        })
+    }
+
+    /// Deserializes a polygon previously written by [`Polygon::to_bytes`].
+    #[staticmethod]
+    pub fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        let data: cache::PolygonData = cache::decode(bytes)?;
+        data.validate().map_err(PyValueError::new_err)?;
+        Ok(Polygon::new(data.ra_verticies, data.dec_verticies))
+    }
+
+    /// Builds the enclosing footprint polygon of a cloud of RA/Dec points,
+    /// via the spherical convex hull of their unit vectors.
+    #[staticmethod]
+    pub fn convex_hull(ras: Vec<f64>, decs: Vec<f64>) -> PyResult<Self> {
+        let (ra_verticies, dec_verticies) =
+            hull::spherical_convex_hull(&ras, &decs).map_err(PyValueError::new_err)?;
+        Ok(Polygon::new(ra_verticies, dec_verticies))
+    }
+}
+
+impl Polygon {
+    pub(crate) fn to_node(&self) -> Node {
+        Node::polygon(self.ra_verticies.clone(), self.dec_verticies.clone())
     }
 }
 
 #[pyclass]
 pub struct Aperture {
     aperture: SphericalAperture,
+    pub(crate) ra_center: f64,
+    pub(crate) dec_center: f64,
+    pub(crate) radius_deg: f64,
 }
 
 #[pymethods]
@@ -52,7 +181,12 @@ impl Aperture {
     #[new]
     pub fn new(ra_center: f64, dec_center: f64, radius_deg: f64) -> Self {
         let sph_app = SphericalAperture::new(ra_center, dec_center, radius_deg);
-        Aperture { aperture: sph_app }
+        Aperture {
+            aperture: sph_app,
+            ra_center,
+            dec_center,
+            radius_deg,
+        }
     }
 
     pub fn is_inside(&self, ra_point: f64, dec_point: f64) -> PointResult {
@@ -63,23 +197,98 @@ impl Aperture {
         }
     }
 
-    pub fn locate_all(&self, ra_points: Vec<f64>, dec_points: Vec<f64>) -> Vec<PointResult> {
-        let results = self.aperture.locate_points(&ra_points, &dec_points);
-        let mut locations: Vec<PointResult> = Vec::new();
-        for result in results {
-            match result {
-                PointLocation::OnBoundary => locations.push(PointResult::Edge),
-                PointLocation::Inside => locations.push(PointResult::Inside),
-                PointLocation::Outside => locations.push(PointResult::Outside),
-            }
+    /// Classifies each (ra, dec) pair in parallel with Rayon. `n_threads`
+    /// defaults to all available cores; pass `1` to run serially.
+    #[pyo3(signature = (ra_points, dec_points, n_threads=None))]
+    pub fn locate_all(
+        &self,
+        py: Python<'_>,
+        ra_points: Vec<f64>,
+        dec_points: Vec<f64>,
+        n_threads: Option<usize>,
+    ) -> Vec<PointResult> {
+        py.allow_threads(|| {
+            parallel::classify(n_threads, &ra_points, &dec_points, |ra, dec| {
+                self.is_inside(ra, dec)
+            })
+        })
+    }
+
+    /// Sky area of the aperture, from the spherical cap solid angle.
+    pub fn area_sq_deg(&self) -> f64 {
+        geometry::steradians_to_sq_deg(geometry::cap_steradians(self.radius_deg))
+    }
+
+    pub fn centroid(&self) -> (f64, f64) {
+        (self.ra_center, self.dec_center)
+    }
+
+    pub fn __or__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Union, self.to_node(), other.into_node())
+    }
+
+    pub fn __and__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Intersection, self.to_node(), other.into_node())
+    }
+
+    pub fn __sub__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Difference, self.to_node(), other.into_node())
+    }
+
+    pub fn __xor__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Xor, self.to_node(), other.into_node())
+    }
+
+    /// Parses a DS9 `circle(ra,dec,radius)` region.
+    #[staticmethod]
+    pub fn from_ds9(text: &str) -> PyResult<Self> {
+        let (shape, values) = ds9::parse_line(text).map_err(PyValueError::new_err)?;
+        if shape != "circle" || values.len() != 3 {
+            return Err(PyValueError::new_err(
+                "expected a DS9 circle(ra,dec,radius) region",
+            ));
         }
-        locations
+        Ok(Aperture::new(values[0], values[1], values[2]))
+    }
+
+    /// Renders this aperture as a DS9 `circle(...)` region line.
+    pub fn to_ds9(&self) -> String {
+        format!(
+            "circle({:.6},{:.6},{:.6})",
+            self.ra_center, self.dec_center, self.radius_deg
+        )
+    }
+
+    /// Serializes this aperture to a compact binary cache format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        cache::encode(&cache::ApertureData {
+            ra_center: self.ra_center,
+            dec_center: self.dec_center,
+            radius_deg: self.radius_deg,
+        })
+    }
+
+    /// Deserializes an aperture previously written by [`Aperture::to_bytes`].
+    #[staticmethod]
+    pub fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        let data: cache::ApertureData = cache::decode(bytes)?;
+        Ok(Aperture::new(data.ra_center, data.dec_center, data.radius_deg))
+    }
+}
+
+impl Aperture {
+    pub(crate) fn to_node(&self) -> Node {
+        Node::aperture(self.ra_center, self.dec_center, self.radius_deg)
     }
 }
 
 #[pyclass]
 pub struct Anulus {
     anulus: SphericalAnulus,
+    pub(crate) ra_center: f64,
+    pub(crate) dec_center: f64,
+    pub(crate) inner_radius_deg: f64,
+    pub(crate) outer_radius_deg: f64,
 }
 
 #[pymethods]
@@ -87,7 +296,13 @@ impl Anulus {
     #[new]
     pub fn new(ra_center: f64, dec_center: f64, inner_radius: f64, outer_radius: f64) -> Self {
         let sph_app = SphericalAnulus::new(ra_center, dec_center, inner_radius, outer_radius);
-        Anulus { anulus: sph_app }
+        Anulus {
+            anulus: sph_app,
+            ra_center,
+            dec_center,
+            inner_radius_deg: inner_radius,
+            outer_radius_deg: outer_radius,
+        }
     }
 
     pub fn is_inside(&self, ra_point: f64, dec_point: f64) -> PointResult {
@@ -98,17 +313,102 @@ impl Anulus {
         }
     }
 
-    pub fn locate_all(&self, ra_points: Vec<f64>, dec_points: Vec<f64>) -> Vec<PointResult> {
-        let results = self.anulus.locate_points(&ra_points, &dec_points);
-        let mut locations: Vec<PointResult> = Vec::new();
-        for result in results {
-            match result {
-                PointLocation::OnBoundary => locations.push(PointResult::Edge),
-                PointLocation::Inside => locations.push(PointResult::Inside),
-                PointLocation::Outside => locations.push(PointResult::Outside),
-            }
+    /// Classifies each (ra, dec) pair in parallel with Rayon. `n_threads`
+    /// defaults to all available cores; pass `1` to run serially.
+    #[pyo3(signature = (ra_points, dec_points, n_threads=None))]
+    pub fn locate_all(
+        &self,
+        py: Python<'_>,
+        ra_points: Vec<f64>,
+        dec_points: Vec<f64>,
+        n_threads: Option<usize>,
+    ) -> Vec<PointResult> {
+        py.allow_threads(|| {
+            parallel::classify(n_threads, &ra_points, &dec_points, |ra, dec| {
+                self.is_inside(ra, dec)
+            })
+        })
+    }
+
+    /// Sky area of the annulus: the outer cap's area minus the inner cap's.
+    pub fn area_sq_deg(&self) -> f64 {
+        geometry::steradians_to_sq_deg(
+            geometry::cap_steradians(self.outer_radius_deg)
+                - geometry::cap_steradians(self.inner_radius_deg),
+        )
+    }
+
+    pub fn centroid(&self) -> (f64, f64) {
+        (self.ra_center, self.dec_center)
+    }
+
+    pub fn __or__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Union, self.to_node(), other.into_node())
+    }
+
+    pub fn __and__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Intersection, self.to_node(), other.into_node())
+    }
+
+    pub fn __sub__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Difference, self.to_node(), other.into_node())
+    }
+
+    pub fn __xor__(&self, other: RegionOperand<'_>) -> CompoundRegion {
+        CompoundRegion::new_op(Op::Xor, self.to_node(), other.into_node())
+    }
+
+    /// Parses a DS9 `annulus(ra,dec,inner,outer)` region.
+    #[staticmethod]
+    pub fn from_ds9(text: &str) -> PyResult<Self> {
+        let (shape, values) = ds9::parse_line(text).map_err(PyValueError::new_err)?;
+        if shape != "annulus" || values.len() != 4 {
+            return Err(PyValueError::new_err(
+                "expected a DS9 annulus(ra,dec,inner,outer) region",
+            ));
         }
-        locations
+        Ok(Anulus::new(values[0], values[1], values[2], values[3]))
+    }
+
+    /// Renders this annulus as a DS9 `annulus(...)` region line.
+    pub fn to_ds9(&self) -> String {
+        format!(
+            "annulus({:.6},{:.6},{:.6},{:.6})",
+            self.ra_center, self.dec_center, self.inner_radius_deg, self.outer_radius_deg
+        )
+    }
+
+    /// Serializes this annulus to a compact binary cache format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        cache::encode(&cache::AnulusData {
+            ra_center: self.ra_center,
+            dec_center: self.dec_center,
+            inner_radius_deg: self.inner_radius_deg,
+            outer_radius_deg: self.outer_radius_deg,
+        })
+    }
+
+    /// Deserializes an annulus previously written by [`Anulus::to_bytes`].
+    #[staticmethod]
+    pub fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        let data: cache::AnulusData = cache::decode(bytes)?;
+        Ok(Anulus::new(
+            data.ra_center,
+            data.dec_center,
+            data.inner_radius_deg,
+            data.outer_radius_deg,
+        ))
+    }
+}
+
+impl Anulus {
+    pub(crate) fn to_node(&self) -> Node {
+        Node::anulus(
+            self.ra_center,
+            self.dec_center,
+            self.inner_radius_deg,
+            self.outer_radius_deg,
+        )
     }
 }
 
@@ -119,5 +419,9 @@ fn regionx(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Polygon>()?;
     m.add_class::<Anulus>()?;
     m.add_class::<Aperture>()?;
+    m.add_class::<RegionCollection>()?;
+    m.add_class::<CompoundRegion>()?;
+    m.add_function(wrap_pyfunction!(load_regions, m)?)?;
+    m.add_function(wrap_pyfunction!(save_regions, m)?)?;
     Ok(())
 }
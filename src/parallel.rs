@@ -0,0 +1,41 @@
+use rayon::prelude::*;
+
+use crate::PointResult;
+
+/// Classifies `ra`/`dec` pairs with `locate`, preserving input order.
+///
+/// `n_threads` follows the convention used across `locate_all`: `None` uses
+/// all available cores, `Some(1)` runs serially on the calling thread, and
+/// any other `Some(n)` runs on a scoped pool of `n` threads.
+pub(crate) fn classify<F>(n_threads: Option<usize>, ra: &[f64], dec: &[f64], locate: F) -> Vec<PointResult>
+where
+    F: Fn(f64, f64) -> PointResult + Sync,
+{
+    if n_threads == Some(1) {
+        return ra
+            .iter()
+            .zip(dec.iter())
+            .map(|(&r, &d)| locate(r, d))
+            .collect();
+    }
+
+    match n_threads {
+        Some(n) => {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .expect("failed to build rayon thread pool");
+            pool.install(|| {
+                ra.par_iter()
+                    .zip(dec.par_iter())
+                    .map(|(&r, &d)| locate(r, d))
+                    .collect()
+            })
+        }
+        None => ra
+            .par_iter()
+            .zip(dec.par_iter())
+            .map(|(&r, &d)| locate(r, d))
+            .collect(),
+    }
+}